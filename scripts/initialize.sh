@@ -17,11 +17,22 @@ CONTRACT_ADDRESS="0x49c90b349fba199c4be542d225d1783ac8c0ddde"
 # Arbitrum Sepolia RPC URL
 RPC_URL="https://sepolia-rollup.arbitrum.io/rpc"
 
+# Initial configuration, applied in the same transaction
+TREASURY="${TREASURY:-0x0000000000000000000000000000000000000000}"
+PROTOCOL_FEE_BPS="${PROTOCOL_FEE_BPS:-0}"                     # at most 2000
+MAX_ANSWER_GRACE="${MAX_ANSWER_GRACE:-0}"                     # seconds
+MAX_OPEN_SESSIONS_PER_HOST="${MAX_OPEN_SESSIONS_PER_HOST:-0}" # 0: unlimited
+SESSION_CREATION_COOLDOWN="${SESSION_CREATION_COOLDOWN:-0}"   # seconds
+SESSION_EXPIRY_PERIOD="${SESSION_EXPIRY_PERIOD:-0}"           # seconds
+PRIZE_CLAIM_WINDOW="${PRIZE_CLAIM_WINDOW:-0}"                 # seconds, 0: never lapse
+
 echo "=========================================="
 echo "Initializing TriviaChain Contract"
 echo "=========================================="
 echo "Contract Address: $CONTRACT_ADDRESS"
 echo "Network: Arbitrum Sepolia"
+echo "Treasury: $TREASURY"
+echo "Protocol fee: $PROTOCOL_FEE_BPS bps"
 echo ""
 
 # Check if private key is set
@@ -41,7 +52,8 @@ echo ""
 
 # Call initialize function using cast
 cast send $CONTRACT_ADDRESS \
-  "initialize()" \
+  "initialize((address,uint256,uint256,uint256,uint256,uint256,uint256))" \
+  "($TREASURY,$PROTOCOL_FEE_BPS,$MAX_ANSWER_GRACE,$MAX_OPEN_SESSIONS_PER_HOST,$SESSION_CREATION_COOLDOWN,$SESSION_EXPIRY_PERIOD,$PRIZE_CLAIM_WINDOW)" \
   --rpc-url $RPC_URL \
   --private-key $SEPOLIA_PRIVATE_KEY \
   --legacy
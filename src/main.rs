@@ -7,4 +7,11 @@ pub extern "C" fn main() {}
 #[cfg(feature = "export-abi")]
 fn main() {
     trivia_chain::print_from_args();
+
+    // Errors, structs and events follow the interface so ITriviaChain.sol
+    // covers everything a Solidity integration needs
+    if std::env::args().len() == 1 {
+        println!();
+        print!("{}", trivia_chain::solidity_declarations());
+    }
 }
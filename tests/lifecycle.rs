@@ -0,0 +1,93 @@
+//! Smoke test for the `test-utils` helpers. Run with
+//! `cargo test --features test-utils`.
+#![cfg(feature = "test-utils")]
+
+use stylus_sdk::{
+    alloy_primitives::{address, Address, FixedBytes, U256},
+    testing::TestVM,
+};
+use trivia_chain::{InitialConfig, SessionRules, SessionStatus, TriviaChain};
+
+const OWNER: Address = address!("0000000000000000000000000000000000000a11");
+const TREASURY: Address = address!("0000000000000000000000000000000000000b22");
+const HOST: Address = address!("0000000000000000000000000000000000000c33");
+const ALICE: Address = address!("0000000000000000000000000000000000000d44");
+const BOB: Address = address!("0000000000000000000000000000000000000e55");
+
+const CURVE_FLAT: u8 = 1;
+
+#[test]
+fn session_runs_from_lobby_to_winner() {
+    let vm = TestVM::default();
+    let mut contract = TriviaChain::deployed(
+        &vm,
+        OWNER,
+        InitialConfig {
+            treasury: TREASURY,
+            protocolFeeBps: U256::ZERO,
+            maxAnswerGrace: U256::ZERO,
+            maxOpenSessionsPerHost: U256::ZERO,
+            sessionCreationCooldown: U256::ZERO,
+            sessionExpiryPeriod: U256::from(86_400),
+            prizeClaimWindow: U256::from(86_400),
+        },
+    );
+
+    let room_code = FixedBytes::from([7u8; 32]);
+    vm.set_sender(HOST);
+    let session_id = contract
+        .create_session(
+            room_code,
+            U256::from(4),
+            U256::from(30),
+            FixedBytes::ZERO,
+            Vec::new(),
+            SessionRules {
+                scoringCurve: CURVE_FLAT,
+                practice: false,
+                passGated: false,
+                blockTiming: false,
+                lateJoin: false,
+                elimination: false,
+                entryFee: U256::ZERO,
+                entryFeeUsdCents: U256::ZERO,
+                stakeAmount: U256::ZERO,
+                minPrizeScore: U256::ZERO,
+                ticketCollection: Address::ZERO,
+                ticketBurn: false,
+                totalQuestions: U256::ZERO,
+                maxAnswerChanges: U256::ZERO,
+            },
+        )
+        .expect("host creates a session");
+
+    vm.set_sender(ALICE);
+    contract
+        .join_session(session_id, room_code, "alice".into())
+        .expect("alice joins");
+    vm.set_sender(BOB);
+    contract
+        .join_session(session_id, room_code, "bob".into())
+        .expect("bob joins");
+
+    vm.set_sender(HOST);
+    contract
+        .start_session(session_id)
+        .expect("host starts the session");
+
+    contract.warp_session(session_id, U256::from(120));
+    contract.inject_score(session_id, ALICE, U256::from(300));
+    contract.inject_score(session_id, BOB, U256::from(500));
+
+    let winner = contract
+        .end_session(session_id)
+        .expect("host ends the session");
+
+    assert_eq!(winner, BOB);
+    assert_eq!(contract.get_winner(session_id), BOB);
+    assert_eq!(contract.get_player(session_id, BOB).score, U256::from(500));
+    assert!(matches!(
+        contract.get_session(session_id).status,
+        SessionStatus::Completed
+    ));
+}